@@ -0,0 +1,45 @@
+// Loads every texture and sound once at startup and exposes them as a single
+// resource, so game systems clone a preloaded handle instead of calling
+// `asset_server.load(...)` inline on every spawn.
+
+use bevy::prelude::*;
+
+// Handles to all the art and audio the game uses. Loading is asynchronous in
+// Bevy, so holding the handles here just keeps the assets alive and avoids
+// re-issuing the same load requests from gameplay systems.
+#[derive(Resource)]
+pub struct GameAssets {
+    pub player: Handle<Image>,
+    pub enemy: Handle<Image>,
+    pub star: Handle<Image>,
+    // Bounce sounds; one is chosen at random whenever an enemy turns.
+    pub hit_sfx: Vec<Handle<AudioSource>>,
+    // Chime played when the player collects a star.
+    pub pickup_sfx: Handle<AudioSource>,
+    // Sound played once when the player is hit and the game ends.
+    pub game_over_sfx: Handle<AudioSource>,
+}
+
+pub struct AssetLoaderPlugin;
+
+impl Plugin for AssetLoaderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreStartup, load_assets);
+    }
+}
+
+// Kick off the loads before any other startup system so the handles are
+// available by the time the first entities spawn.
+fn load_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameAssets {
+        player: asset_server.load("sprites/ball_blue_large.png"),
+        enemy: asset_server.load("sprites/ball_red_large.png"),
+        star: asset_server.load("sprites/star.png"),
+        hit_sfx: vec![
+            asset_server.load("audio/pluck_001.ogg"),
+            asset_server.load("audio/pluck_002.ogg"),
+        ],
+        pickup_sfx: asset_server.load("audio/laserLarge_000.ogg"),
+        game_over_sfx: asset_server.load("audio/explosionCrunch_000.ogg"),
+    });
+}
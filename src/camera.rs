@@ -0,0 +1,51 @@
+// Owns the 2D camera: spawned once at startup and smoothly chasing whichever
+// entity carries the CameraTarget marker.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+// Marks the entity the camera should smoothly track. Keeping it a separate
+// marker means the follow target is data-driven rather than hard-wired to the
+// player, so it can be moved to another entity later without touching the system.
+#[derive(Component)]
+pub struct CameraTarget;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_camera)
+            // Chase the target after the simulation has moved it.
+            .add_systems(PostUpdate, follow_player);
+    }
+}
+
+// create a camera in the game, also centered in the window
+pub fn spawn_camera(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>
+){
+    let window = window_query.get_single().unwrap();
+    commands.spawn(Camera2dBundle {
+        transform: Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
+        ..default()
+    });
+}
+
+// Each frame, ease the camera toward the follow target instead of snapping, so
+// the view glides as the player moves around the growing arena.
+pub fn follow_player(
+    time: Res<Time>,
+    target_query: Query<&Transform, (With<CameraTarget>, Without<Camera>)>,
+    mut camera_query: Query<&mut Transform, With<Camera>>,
+){
+    let Ok(target) = target_query.get_single() else {
+        return;
+    };
+    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
+        let target = Vec3::new(target.translation.x, target.translation.y, camera_transform.translation.z);
+        camera_transform.translation = camera_transform
+            .translation
+            .lerp(target, 5.0 * time.delta_seconds());
+    }
+}
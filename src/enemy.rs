@@ -0,0 +1,219 @@
+// Owns the enemy balls: spawning the initial set, periodically adding more,
+// the dice-roll wander AI, and the game-over hit detection.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_ggrs::{AddRollbackCommandExtension, GgrsSchedule};
+use bevy_rapier2d::prelude::*;
+use rand::{thread_rng, Rng};
+
+use crate::assets::GameAssets;
+use crate::player::Player;
+use crate::{
+    no_player_exists, not_paused, AppState, GameFlow, Phase, RollbackRng, ENEMY_SIZE,
+    ENEMY_SPAWN_TIME, ENEMY_SPEED, FIXED_TIMESTEP, NUMBER_OF_ENEMIES,
+};
+
+#[derive(Component, Clone, Copy)]
+pub struct Enemy {
+    pub direction: Vec2,
+}
+
+// Non-rollback record of the heading the bounce SFX last reacted to. Kept off
+// the rollback set so a re-simulated turn doesn't re-spawn the sound; the audio
+// is emitted from a plain Update system that compares this to `Enemy::direction`.
+#[derive(Component)]
+pub struct EnemyBounceFx {
+    pub last_dir: Vec2,
+}
+
+// Fires on a repeating interval to add another enemy to the arena.
+#[derive(Resource, Clone)]
+pub struct EnemySpawnTimer {
+    pub timer: Timer,
+}
+
+impl Default for EnemySpawnTimer {
+    fn default() -> Self {
+        EnemySpawnTimer {
+            timer: Timer::from_seconds(ENEMY_SPAWN_TIME, TimerMode::Repeating),
+        }
+    }
+}
+
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemySpawnTimer>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                spawn_enemies.run_if(no_player_exists),
+            )
+            .add_systems(
+                GgrsSchedule,
+                (
+                    // Wander AI and spawning set up bodies before the step.
+                    (update_enemy_direction, spawn_enemies_over_time)
+                        .before(PhysicsSet::SyncBackend),
+                    // Hit detection reads the poses the step just wrote back.
+                    enemy_hit_player.after(PhysicsSet::Writeback),
+                )
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(not_paused),
+            )
+            // Cosmetic only, so it reacts to confirmed heading changes outside
+            // the rollback schedule instead of spawning audio mid-simulation.
+            .add_systems(
+                Update,
+                react_enemy_bounce.run_if(in_state(AppState::Playing)),
+            );
+    }
+}
+
+pub fn spawn_enemies(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    assets: Res<GameAssets>,
+    mut rng: ResMut<RollbackRng>,
+){
+    let window = window_query.get_single().unwrap();
+    for _ in 0..NUMBER_OF_ENEMIES {
+        let random_x = rng.unit() * window.width();
+        let random_y = rng.unit() * window.height();
+        let direction = Vec2::new(rng.unit(), rng.unit()).normalize();
+
+        commands
+            .spawn((
+                SpriteBundle{
+                    transform: Transform::from_xyz(random_x, random_y, 0.0),
+                    texture: assets.enemy.clone(),
+                    ..default()
+                },
+                Enemy { direction },
+                EnemyBounceFx { last_dir: direction },
+                // Dynamic body with perfect restitution so it bounces off the
+                // arena walls physically rather than via a velocity-flip hack.
+                RigidBody::Dynamic,
+                Collider::ball(ENEMY_SIZE / 2.0),
+                Velocity::linear(direction * ENEMY_SPEED),
+                Restitution::coefficient(1.0),
+                LockedAxes::ROTATION_LOCKED,
+            ))
+            .add_rollback();
+    };
+
+}
+
+// add another enemy at a random position whenever the enemy timer fires
+pub fn spawn_enemies_over_time(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    assets: Res<GameAssets>,
+    mut enemy_timer: ResMut<EnemySpawnTimer>,
+    mut rng: ResMut<RollbackRng>,
+) {
+    // Fixed step keeps the spawn cadence identical on both peers.
+    enemy_timer
+        .timer
+        .tick(std::time::Duration::from_secs_f32(FIXED_TIMESTEP));
+
+    if enemy_timer.timer.finished() {
+        let window = window_query.get_single().unwrap();
+        let random_x = rng.unit() * window.width();
+        let random_y = rng.unit() * window.height();
+        let direction = Vec2::new(rng.unit(), rng.unit()).normalize();
+
+        commands
+            .spawn((
+                SpriteBundle {
+                    transform: Transform::from_xyz(random_x, random_y, 0.0),
+                    texture: assets.enemy.clone(),
+                    ..default()
+                },
+                Enemy { direction },
+                EnemyBounceFx { last_dir: direction },
+                RigidBody::Dynamic,
+                Collider::ball(ENEMY_SIZE / 2.0),
+                Velocity::linear(direction * ENEMY_SPEED),
+                Restitution::coefficient(1.0),
+                LockedAxes::ROTATION_LOCKED,
+            ))
+            .add_rollback();
+    }
+}
+
+// Pick a fresh unit heading by sampling a random angle around the circle.
+fn random_direction(rng: &mut impl Rng) -> Vec2 {
+    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+    Vec2::new(angle.cos(), angle.sin())
+}
+
+pub fn update_enemy_direction(
+    mut enemy_query: Query<(&mut Velocity, &mut Enemy)>,
+    // The shared deterministic RNG, so both peers roll identical dice.
+    mut rng: ResMut<RollbackRng>,
+){
+    for (mut velocity, mut enemy) in enemy_query.iter_mut(){
+        // "Roll the dice" each tick: with low probability, strike out in a
+        // brand-new random direction; otherwise keep drifting. This makes the
+        // enemies wander unpredictably instead of marching in straight lines.
+        // Wall reflection is now handled physically by the arena colliders, so
+        // this is the only place we ever change heading deliberately; the bounce
+        // sound is driven off the confirmed heading change in react_enemy_bounce.
+        if rng.rng.gen_range(0..60) == 0 {
+            enemy.direction = random_direction(&mut rng.rng);
+            velocity.linvel = enemy.direction * ENEMY_SPEED;
+        }
+    }
+}
+
+// Play a bounce sound whenever an enemy's confirmed heading has changed since we
+// last looked. Running outside GgrsSchedule means a rollback re-simulating the
+// turn doesn't re-spawn the audio; the sound choice uses the thread RNG because
+// it is purely cosmetic and must not touch the rolled-back RollbackRng.
+pub fn react_enemy_bounce(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut enemy_query: Query<(&Enemy, &mut EnemyBounceFx)>,
+){
+    for (enemy, mut fx) in enemy_query.iter_mut() {
+        if enemy.direction != fx.last_dir {
+            let index = thread_rng().gen_range(0..assets.hit_sfx.len());
+            commands.spawn(AudioBundle {
+                source: assets.hit_sfx[index].clone(),
+                settings: PlaybackSettings::DESPAWN,
+            });
+            fx.last_dir = enemy.direction;
+        }
+    }
+}
+
+pub fn enemy_hit_player(
+    mut commands: Commands,
+    // Rapier's contact graph for the step we just integrated; querying it here
+    // is deterministic, unlike draining render-cadence CollisionEvents.
+    rapier_context: Res<RapierContext>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    enemy_query: Query<Entity, With<Enemy>>,
+    // Game over is driven through the rolled-back phase, not NextState, so a
+    // mispredicted hit that a later rollback erases un-does the transition too.
+    mut flow: ResMut<GameFlow>,
+){
+    for (player_entity, transform) in player_query.iter() {
+        // A player touching any enemy ends the game.
+        let hit = enemy_query.iter().any(|enemy_entity| {
+            rapier_context
+                .contact_pair(player_entity, enemy_entity)
+                .map_or(false, |pair| pair.has_any_active_contact())
+        });
+
+        if hit {
+            println!("Enemy hit player! Game over!");
+            // Record where this player died so the death burst lands on it.
+            flow.death_pos = transform.translation;
+            commands.entity(player_entity).despawn_recursive();
+            flow.phase = Phase::GameOver;
+        }
+    }
+}
@@ -4,9 +4,181 @@
 // System = are where the logic and behavior of the game are implemented
 //        = operate on entities that have specific components (e.g., a "MovementSystem" might update the position of all entities that have both a "Position" component and a "Velocity" component)
 
+use std::net::SocketAddr;
+
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
-use rand::prelude::*;
+use bevy_ggrs::prelude::*;
+use bevy_ggrs::{GgrsApp, LocalInputs, LocalPlayers, PlayerInputs};
+use bevy_rapier2d::prelude::*;
+use ggrs::{PlayerType, UdpNonBlockingSocket};
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+// Each feature area owns its components, resources and systems in its own
+// module and exposes them as a Bevy plugin; main just wires the plugins and the
+// cross-cutting concerns (state machine, netcode, pause, menu/game-over UI) together.
+mod assets;
+mod camera;
+mod enemy;
+mod particles;
+mod player;
+mod star;
+
+use assets::AssetLoaderPlugin;
+use camera::CameraPlugin;
+use enemy::{Enemy, EnemyPlugin, EnemySpawnTimer};
+use particles::ParticlePlugin;
+use player::{Player, PlayerPlugin};
+use star::{Score, Star, StarPlugin, StarSpawnTimer};
+
+// --- Rollback netcode -------------------------------------------------------
+// The simulation runs on a fixed 60 FPS step driven by GGRS so that both peers
+// re-simulate identically after a rollback. Nothing in a rollback system may
+// read wall-clock `Time`; they use FIXED_TIMESTEP instead.
+pub const FPS: usize = 60;
+pub const FIXED_TIMESTEP: f32 = 1.0 / FPS as f32;
+pub const NUM_PLAYERS: usize = 2;
+
+// Per-frame input for one player, packed as a bitmask of the held directions.
+pub const INPUT_UP: u8 = 1 << 0;
+pub const INPUT_DOWN: u8 = 1 << 1;
+pub const INPUT_LEFT: u8 = 1 << 2;
+pub const INPUT_RIGHT: u8 = 1 << 3;
+// Pause is part of the shared input so both peers pause on the same simulated
+// frame; a purely local pause would desync lockstep.
+pub const INPUT_PAUSE: u8 = 1 << 4;
+// Start/restart is shared too, so Menu->Playing and GameOver->Playing happen on
+// the same simulated frame on both peers rather than off a local key press.
+pub const INPUT_START: u8 = 1 << 5;
+
+// GGRS needs to know the concrete types used for input, saved state and peer
+// addressing. Input is our direction bitmask; the rolled-back world state is
+// snapshotted by bevy_ggrs through the clone/copy registrations in main, so
+// `State` is only a placeholder; peers are addressed by UDP socket address.
+#[derive(Debug)]
+pub struct GgrsConfig;
+
+impl ggrs::Config for GgrsConfig {
+    type Input = u8;
+    type State = u8;
+    type Address = SocketAddr;
+}
+
+// Shared deterministic RNG. Both peers seed it from the same session seed so
+// spawn_enemies/spawn_stars lay out identical worlds; never use the thread rng
+// for anything that affects simulation state.
+#[derive(Resource, Clone)]
+pub struct RollbackRng {
+    pub rng: StdRng,
+}
+
+impl RollbackRng {
+    pub fn new(seed: u64) -> Self {
+        RollbackRng {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    // Random float in [0, 1), mirroring the old `random::<f32>()` calls.
+    pub fn unit(&mut self) -> f32 {
+        self.rng.gen::<f32>()
+    }
+}
+
+// Parse `--local-port <port>`, `--remote <addr>` and `--seed <n>` from the CLI
+// and build a 2-player P2P session. The local player always takes handle 0. The
+// world-generation seed comes from `--seed` (default `0`); both peers must pass
+// the same value out of band so `spawn_enemies`/`spawn_stars` lay out identical
+// worlds.
+fn start_session() -> (P2PSession<GgrsConfig>, u64) {
+    let mut local_port: u16 = 7000;
+    let mut remote_addr: Option<SocketAddr> = None;
+    let mut seed: u64 = 0;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--local-port" => {
+                local_port = args
+                    .next()
+                    .and_then(|v| v.parse().ok())
+                    .expect("--local-port expects a port number");
+            }
+            "--remote" => {
+                remote_addr = Some(
+                    args.next()
+                        .and_then(|v| v.parse().ok())
+                        .expect("--remote expects a socket address"),
+                );
+            }
+            "--seed" => {
+                seed = args
+                    .next()
+                    .and_then(|v| v.parse().ok())
+                    .expect("--seed expects a number");
+            }
+            _ => {}
+        }
+    }
+
+    let mut builder = SessionBuilder::<GgrsConfig>::new()
+        .with_num_players(NUM_PLAYERS)
+        .with_fps(FPS)
+        .expect("invalid fps");
+
+    // Local player is handle 0, the remote peer is handle 1.
+    builder = builder
+        .add_player(PlayerType::Local, 0)
+        .expect("failed to add local player");
+    let remote = remote_addr.expect("--remote <addr> is required to connect to a peer");
+    builder = builder
+        .add_player(PlayerType::Remote(remote), 1)
+        .expect("failed to add remote player");
+
+    let socket = UdpNonBlockingSocket::bind_to_port(local_port).expect("failed to bind socket");
+    let session = builder
+        .start_p2p_session(socket)
+        .expect("failed to start session");
+
+    (session, seed)
+}
+
+// Collect the local player's held directions into the input bitmask GGRS sends
+// to the peer each frame.
+fn read_local_inputs(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    local_players: Res<LocalPlayers>,
+) {
+    let mut local_inputs = bevy::utils::HashMap::new();
+
+    for &handle in &local_players.0 {
+        let mut input = 0u8;
+        if keyboard_input.pressed(KeyCode::Up) || keyboard_input.pressed(KeyCode::W) {
+            input |= INPUT_UP;
+        }
+        if keyboard_input.pressed(KeyCode::Down) || keyboard_input.pressed(KeyCode::S) {
+            input |= INPUT_DOWN;
+        }
+        if keyboard_input.pressed(KeyCode::Left) || keyboard_input.pressed(KeyCode::A) {
+            input |= INPUT_LEFT;
+        }
+        if keyboard_input.pressed(KeyCode::Right) || keyboard_input.pressed(KeyCode::D) {
+            input |= INPUT_RIGHT;
+        }
+        if keyboard_input.pressed(KeyCode::P) {
+            input |= INPUT_PAUSE;
+        }
+        if keyboard_input.pressed(KeyCode::Return) {
+            input |= INPUT_START;
+        }
+        local_inputs.insert(handle, input);
+    }
+
+    commands.insert_resource(LocalInputs::<GgrsConfig>(local_inputs));
+}
 
 pub const PLAYER_SPEED: f32 = 500.0;
 pub const PLAYER_SIZE: f32 = 64.0; // player sprite size
@@ -15,6 +187,8 @@ pub const ENEMY_SPEED: f32 = 200.0;
 pub const ENEMY_SIZE: f32 = 64.0;
 pub const NUMBER_OF_STARS: usize = 10;
 pub const STAR_SIZE: f32 = 30.0;
+// Thickness of the invisible static walls that confine the arena.
+pub const WALL_THICKNESS: f32 = 20.0;
 
 
 // Commands: Used to create or modify entities in the game.
@@ -25,329 +199,393 @@ pub const STAR_SIZE: f32 = 30.0;
 // KeyCode: Represents keyboard keys.
 // Res and ResMut: Used to access shared resources in a read-only or mutable way.
 
+// The high-level screen the game is currently on.
+// States let us gate systems so they only run on the relevant screen instead of
+// wiring everything unconditionally into Update.
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum AppState {
+    // Title screen with a "press Enter to start" prompt.
+    #[default]
+    Menu,
+    // The actual gameplay loop.
+    Playing,
+    // The player has been hit; offers a restart.
+    GameOver,
+}
+
+// Whether the shared simulation is currently paused. This is rolled-back state
+// driven by the synchronized pause input, not a local AppState screen, so both
+// peers freeze on the same frame and a rollback restores the pause correctly.
+#[derive(Resource, Clone, Copy, Default)]
+pub struct PauseState {
+    pub paused: bool,
+    // Pause bit held on the previous simulated frame, for rising-edge detection.
+    pub prev_pressed: bool,
+}
+
+// The authoritative game phase. It is advanced only inside the fixed step — from
+// the synchronized start input and from confirmed collisions — and rolls back
+// with the rest of the simulation, so both peers change phase on the same frame
+// and a rollback that erases a contact also un-does the game-over it caused.
+// AppState mirrors this each frame (sync_app_state) so the menu / game-over UI
+// and system gating stay driven by deterministic state.
+#[derive(Resource, Clone, Copy, Default)]
+pub struct GameFlow {
+    pub phase: Phase,
+    // Start/restart bit held on the previous simulated frame, for edge detection.
+    pub prev_start: bool,
+    // Where the fatal hit happened, recorded from the hit player's transform so
+    // the death burst can be placed there regardless of which player died.
+    pub death_pos: Vec3,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum Phase {
+    #[default]
+    Menu,
+    Playing,
+    GameOver,
+}
+
+// How often a fresh star pops into the arena.
+pub const STAR_SPAWN_TIME: f32 = 1.0;
+// How often another enemy joins to ramp up the difficulty.
+pub const ENEMY_SPAWN_TIME: f32 = 5.0;
+
+// Marker for UI entities that only exist on the menu screen, so we can despawn
+// the whole menu in one query when gameplay starts.
+#[derive(Component)]
+pub struct MenuUi;
+
+// Marker for the game-over overlay UI.
+#[derive(Component)]
+pub struct GameOverUi;
+
 fn main() {
+    // Build the peer-to-peer session from the CLI before the app starts so the
+    // shared seed is known when resources are inserted.
+    let (session, seed) = start_session();
+
     // Default plugins - allow us to get rendering Windows, UI, audio, and other funtionality
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_systems(Startup,(spawn_player, spawn_enemies, spawn_stars))
-        .add_systems(Update,(spawn_camera, player_movement, confine_player_movement, enemy_movement, update_enemy_direction, confine_enemy_movement, enemy_hit_player, player_hit_star))
+        .add_plugins(GgrsPlugin::<GgrsConfig>::default())
+        // Real 2D physics: colliders + restitution replace the hand-written
+        // distance tests and window-clamp blocks. Rapier's own scheduling is
+        // turned off so the whole pipeline runs inside GgrsSchedule on the fixed
+        // step (wired below). Note we do NOT roll back RapierContext itself (its
+        // island/sleep state and contact manifolds used for warm-starting are not
+        // saved): instead each step re-derives body poses and velocities from the
+        // rolled-back Transform/Velocity components via SyncBackend, which GGRS
+        // marks changed on restore. Residual warm-start drift is negligible for
+        // this small non-stacking arena.
+        .add_plugins(
+            RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0)
+                .with_default_system_setup(false),
+        )
+        // Each feature area is a self-contained plugin owning its own systems.
+        .add_plugins((
+            AssetLoaderPlugin,
+            CameraPlugin,
+            PlayerPlugin,
+            EnemyPlugin,
+            StarPlugin,
+            ParticlePlugin,
+        ))
+        .insert_resource(Session::P2P(session))
+        // Integrate physics on the fixed GGRS step instead of the wall clock so
+        // both peers advance identically and a rollback can re-simulate it. Zero
+        // gravity keeps the top-down arena flat.
+        .insert_resource(RapierConfiguration {
+            gravity: Vec2::ZERO,
+            timestep_mode: TimestepMode::Fixed {
+                dt: FIXED_TIMESTEP,
+                substeps: 1,
+            },
+            ..default()
+        })
+        // The direction bitmask for the local player is read once per frame.
+        .set_rollback_schedule_fps(FPS)
+        .add_systems(ReadInputs, read_local_inputs)
+        // Run the rapier pipeline inside the rollback schedule, after the
+        // gameplay systems have set velocities for the step.
+        .configure_sets(
+            GgrsSchedule,
+            (
+                PhysicsSet::SyncBackend,
+                PhysicsSet::StepSimulation,
+                PhysicsSet::Writeback,
+            )
+                .chain(),
+        )
+        .add_systems(
+            GgrsSchedule,
+            (
+                RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::SyncBackend)
+                    .in_set(PhysicsSet::SyncBackend),
+                RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::StepSimulation)
+                    .in_set(PhysicsSet::StepSimulation),
+                RapierPhysicsPlugin::<NoUserData>::get_systems(PhysicsSet::Writeback)
+                    .in_set(PhysicsSet::Writeback),
+            )
+                // Freeze integration while the shared pause flag is set.
+                .run_if(in_state(AppState::Playing))
+                .run_if(not_paused),
+        )
+        // The pause toggle itself runs every simulated frame (so it can unpause)
+        // and before the systems it gates.
+        .add_systems(
+            GgrsSchedule,
+            toggle_pause
+                .before(PhysicsSet::SyncBackend)
+                .before(player::player_movement)
+                .before(enemy::update_enemy_direction)
+                .before(enemy::spawn_enemies_over_time)
+                .before(star::spawn_stars_over_time)
+                .run_if(in_state(AppState::Playing)),
+        )
+        // Phase advancement runs every simulated frame regardless of phase so it
+        // can start the game from Menu/GameOver on the shared input.
+        .add_systems(GgrsSchedule, advance_game_phase.before(PhysicsSet::SyncBackend))
+        // Any component that simulation (including the physics step) mutates must
+        // be rolled back: poses via Transform, and the integrated velocities.
+        .rollback_component_with_clone::<Transform>()
+        .rollback_component_with_copy::<Velocity>()
+        .rollback_component_with_copy::<Enemy>()
+        .add_state::<AppState>()
+        .insert_resource(RollbackRng::new(seed))
+        // The shared RNG is mutated by simulation systems, so its StdRng state
+        // must be saved and restored with every rollback; otherwise re-simulated
+        // frames draw fresh numbers and the two peers diverge.
+        .rollback_resource_with_clone::<RollbackRng>()
+        // The score is bumped inside the simulated pickup system, so it must roll
+        // back too; otherwise every re-simulation re-counts the same star.
+        .rollback_resource_with_copy::<Score>()
+        // The pause flag is simulation state too, so it rolls back with the rest.
+        .insert_resource(PauseState::default())
+        .rollback_resource_with_copy::<PauseState>()
+        // The spawn timers are ticked inside the fixed step, so they must roll
+        // back as well; otherwise a peer that rolls back more often advances the
+        // spawn cadence faster and the RNG draw counts diverge.
+        .rollback_resource_with_clone::<StarSpawnTimer>()
+        .rollback_resource_with_clone::<EnemySpawnTimer>()
+        // The game phase is deterministic simulation state, advanced from the
+        // synchronized start input and confirmed hits, so it rolls back too.
+        .insert_resource(GameFlow::default())
+        .rollback_resource_with_copy::<GameFlow>()
+        // Mirror the deterministic game phase onto AppState so the UI and the
+        // OnEnter/OnExit wiring below stay driven by rolled-back state.
+        .add_systems(Update, sync_app_state)
+        // --- Menu ---
+        .add_systems(OnEnter(AppState::Menu), spawn_menu)
+        .add_systems(OnExit(AppState::Menu), despawn_menu)
+        // --- Playing ---
+        // The arena walls are seeded alongside the per-plugin spawns, and only
+        // while the arena is empty so a second set never stacks on a live arena.
+        .add_systems(
+            OnEnter(AppState::Playing),
+            spawn_walls.run_if(no_player_exists),
+        )
+        // --- Game over ---
+        .add_systems(OnEnter(AppState::GameOver), spawn_game_over)
+        // Clear the old run's entities when leaving the game-over screen so the
+        // fresh Playing spawns don't stack on top of the previous arena.
+        .add_systems(OnExit(AppState::GameOver), (despawn_game_over, cleanup_arena))
         // start the game loop
         .run();
 }
 
-// Component
+// One of the four static colliders bounding the play area. Enemies bounce off
+// these via restitution and the player is simply stopped by them.
 #[derive(Component)]
-pub struct Player {}
-
-#[derive(Component)]
-pub struct Enemy {
-    pub direction: Vec2,
-}
-
-#[derive(Component)]
-pub struct Star {}
+pub struct AreaWall;
 //Entity
-// use "Commands" to spawn entities (creating a new object), despawn entities, add components to entities, remove components from entities 
+// use "Commands" to spawn entities (creating a new object), despawn entities, add components to entities, remove components from entities
 // query window to get information about the width and height
 // access to the asset server through a resource in order to load in our PNG file
 // BEVY create an entity with a window and primary window component for us as well as a resource holding the asset server
 
-// 1. create player entity
-// 2. sets the player's position to the center od the game window
-// 3. loads a sprite image for the player
-pub fn spawn_player(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    // a Resource<T> is a unique and globally accessible struct
-    // Only one Resource of each type <T> can exist at an given time
-    // We can use Resources in our systems as system parameters using Res<T> (Read-only), ResMut<T> (Mutable)
-    // Res<T> and ResMut<T> are used to access shared resources in a read-only or mutable way.
-    asset_server: Res<AssetServer>,
-){
-    // Getting a reference to our window query
-    // The get_single() method 
-    // only one entity will exist with both window component & primary window component
-    // Since you are querying for the Window component with the PrimaryWindow marker, get_single() is used to get the primary game window.
-    let window = window_query.get_single().unwrap();
-
-    commands.spawn((
-        // Bundles - can quickly add/remove sets of components to or from an entity
-        SpriteBundle{
-            transform: Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
-            texture: asset_server.load("sprites/ball_blue_large.png"),
-            ..default()
-        },
-        Player {},
-    ));
-}
-
-
-// create a camera in the game, also centered in the window
-pub fn spawn_camera(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>
-){
-    let window = window_query.get_single().unwrap();
-    commands.spawn(Camera2dBundle {
-        transform: Transform::from_xyz(window.width() / 2.0, window.height() / 2.0, 0.0),
-        ..default()
-    });
-}
-
-pub fn spawn_enemies(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
-){
-    let window = window_query.get_single().unwrap();
-    for _ in 0..NUMBER_OF_ENEMIES {
-        let random_x = random::<f32>() * window.width();
-        let random_y = random::<f32>() * window.height();
-
-        commands.spawn(
-            (
-                SpriteBundle{
-                    transform: Transform::from_xyz(random_x, random_y, 0.0),
-                    texture: asset_server.load("sprites/ball_red_large.png"),
+// draws the title screen and the "press Enter to start" prompt
+pub fn spawn_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
                     ..default()
                 },
-                Enemy {
-                    direction: Vec2::new(random::<f32>(), random::<f32>()).normalize(),
-                },
-            )
-        );
-    };
-
-}
-
-pub fn spawn_stars(
-    mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    asset_server: Res<AssetServer>,
-){
-    let window = window_query.get_single().unwrap();
-    for _ in 0..NUMBER_OF_STARS{
-        let random_x = random::<f32>() * window.width();
-        let random_y = random::<f32>() * window.height();
-
-        commands.spawn((
-            SpriteBundle{
-                transform: Transform::from_xyz(random_x, random_y, 0.0),
-                texture: asset_server.load("sprites/star.png"),
                 ..default()
             },
-            Star{},
-        ));
-    }
+            MenuUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Ball Game",
+                TextStyle {
+                    font_size: 80.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                "Press Enter to start",
+                TextStyle {
+                    font_size: 32.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
 }
 
-
-// handles player's movement
-pub fn player_movement(
-    // resource to keyboard input
-    keyboard_input: Res<Input<KeyCode>>,
-    mut player_query: Query<&mut Transform, With<Player>>,
-    time: Res<Time>,
-){
-    // get_single_mut give result type with Result<T,E>
-    // if let is a syntax in Rust used for pattern matching. 
-    // Ok(mut transform): This pattern matches if the result of player_query.get_single_mut() is Ok, meaning it successfully found the Transform component of the player entity.
-    if let Ok(mut transform) = player_query.get_single_mut(){
-        // Vec3 = 3-dimensional vector 
-        // Vec3::ZERO =  (0, 0, 0) = no movement
-        let mut direction = Vec3::ZERO;
-
-        if keyboard_input.pressed(KeyCode::Left) || keyboard_input.pressed(KeyCode::A){
-            direction += Vec3::new(-1.0,0.0,0.0);
-        }
-        if keyboard_input.pressed(KeyCode::Right) || keyboard_input.pressed(KeyCode::D){
-            direction += Vec3::new(1.0,0.0,0.0);
-        }
-        if keyboard_input.pressed(KeyCode::Up) || keyboard_input.pressed(KeyCode::W){
-            direction += Vec3::new(0.0,1.0,0.0);
-        }
-        if keyboard_input.pressed(KeyCode::Down) || keyboard_input.pressed(KeyCode::S){
-            direction += Vec3::new(0.0,-1.0,0.0);
-        }
-
-        // direction.normalize() normalizes the vector
-        // Normalization is a process that adjusts the vector so that its length (magnitude) is exactly 1, but it keeps pointing in the same direction.
-        if direction.length() > 0.0 {
-            direction = direction.normalize();
-        }
-
-        // delta_seconds returns the time elapsed since the last frame/update in seconds.
-        // Multiplying by time.delta_seconds() ensures that your movement is frame-rate independent. That means the entity will move at the same speed regardless of how fast the game loop is running.
-        // "update the entity's position by moving it in the specified direction, at a certain speed, for the amount of time that has passed since the last frame." 
-        transform.translation += direction * PLAYER_SPEED * time.delta_seconds();
+pub fn despawn_menu(mut commands: Commands, menu_query: Query<Entity, With<MenuUi>>) {
+    for entity in menu_query.iter() {
+        commands.entity(entity).despawn_recursive();
     }
 }
 
-// ensure the player doesnt move outside the game window
-pub fn confine_player_movement(
-    mut player_query: Query<&mut Transform, With<Player>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-){
-    if let Ok(mut player_transform) = player_query.get_single_mut(){
-        let window = window_query.get_single().unwrap();
-
-        // The division by 2.0 is to get the radius (half the size) of the player if it's a square or circular sprite
-        let half_player_size = PLAYER_SIZE / 2.0;
-        // The leftmost point where the player can go without half of it going off-screen.
-        let x_min = 0.0 + half_player_size;
-        // The rightmost point where the player can go without going off the right edge of the screen.
-        let x_max = window.width() - half_player_size;
-        // The lowest point the player can go without going off the bottom edge of the screen.
-        let y_min = 0.0 + half_player_size;
-        // The highest point the player can go without going off the top edge of the screen
-        let y_max = window.height() - half_player_size;
-
-        // gets the current position of the player in the game window.
-        let mut translation = player_transform.translation;
-
-        // Bound the player x position
-        if translation.x < x_min{
-            translation.x = x_min;
-        }else if translation.x > x_max{
-            translation.x = x_max;
-        }
-
-        // Bound the players y position
-        if translation.y < y_min {
-            translation.y = y_min;
-        }else if translation.y > y_max {
-            translation.y = y_max;
+// Advance the shared phase from the synchronized start input: the rising edge of
+// any player's start bit starts a fresh run from the menu or the game-over
+// screen. Runs inside the fixed step so both peers transition on the same frame.
+pub fn advance_game_phase(inputs: Res<PlayerInputs<GgrsConfig>>, mut flow: ResMut<GameFlow>) {
+    let pressed = (0..NUM_PLAYERS).any(|handle| inputs[handle].0 & INPUT_START != 0);
+    if pressed && !flow.prev_start {
+        match flow.phase {
+            Phase::Menu | Phase::GameOver => flow.phase = Phase::Playing,
+            Phase::Playing => {}
         }
-
-        // applies the adjusted position back to the player
-        player_transform.translation = translation;
     }
-
+    flow.prev_start = pressed;
 }
 
-pub fn enemy_movement(
-    mut enemy_query: Query<(&mut Transform, &Enemy)>, time: Res<Time>){
-        for (mut transform, enemy) in enemy_query.iter_mut() {
-            let direction = Vec3::new(enemy.direction.x, enemy.direction.y, 0.0);
-            transform.translation += direction * ENEMY_SPEED * time.delta_seconds();
-        }
+// Mirror the deterministic game phase onto AppState. Because GameFlow is rolled
+// back and identical on both peers, the mirror — and therefore every OnEnter /
+// OnExit transition — fires on the same frame on both machines.
+pub fn sync_app_state(
+    flow: Res<GameFlow>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let desired = match flow.phase {
+        Phase::Menu => AppState::Menu,
+        Phase::Playing => AppState::Playing,
+        Phase::GameOver => AppState::GameOver,
+    };
+    if *state.get() != desired {
+        next_state.set(desired);
     }
+}
 
-pub fn update_enemy_direction(
-    mut enemy_query: Query<(&Transform, &mut Enemy)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    // audio: Res<Audio>,
-    // asset_server: Res<AssetServer>,
-){
-    let window = window_query.get_single().unwrap();
-    let half_enemy_size: f32 = ENEMY_SIZE / 2.0;
-    let x_min: f32 = 0.0 + half_enemy_size;
-    let x_max: f32 = window.width() - half_enemy_size;
-    let y_min: f32 = 0.0 + half_enemy_size;
-    let y_max: f32 = window.height() - half_enemy_size;
-
-    for (transform, mut enemy) in enemy_query.iter_mut(){
-        // let mut direction_changed = false;
-
-        let translation = transform.translation;
-        if translation.x < x_min || translation.x > x_max {
-            enemy.direction.x *= -1.0;
-            // direction_changed = true;
-
-        }
-        if translation.y < y_min || translation.y > y_max {
-            enemy.direction.y *= -1.0;
-            // direction_changed = true;
-        }
-
-        // Play SFX
-        // if direction_changed {
-        //     // Play Sound Effect
-        //     let sound_effect_1 = asset_server.load("audio/pluck_001.ogg");
-        //     let sound_effect_2 = asset_server.load("audio/pluck_002.ogg");
-
-        //     // Randomly play one of the two sound effects
-        //     let sound_effect = if random::<f32>() > 0.5 {
-        //         sound_effect_1
-        //     } else {
-        //         sound_effect_2
-        //     };
-            // audio.play(sound_effect);
-        // }
+// Flip the shared pause flag on the rising edge of any player's pause bit. This
+// runs inside the fixed step (regardless of the pause flag, so it can unpause)
+// so both peers toggle on the same simulated frame.
+pub fn toggle_pause(inputs: Res<PlayerInputs<GgrsConfig>>, mut pause: ResMut<PauseState>) {
+    let pressed = (0..NUM_PLAYERS).any(|handle| inputs[handle].0 & INPUT_PAUSE != 0);
+    if pressed && !pause.prev_pressed {
+        pause.paused = !pause.paused;
     }
+    pause.prev_pressed = pressed;
 }
 
-pub fn confine_enemy_movement(
-    mut enemy_query: Query <&mut Transform, With<Enemy>>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-){
-    let window = window_query.get_single().unwrap();
-
-    let half_enemy_size: f32 = ENEMY_SIZE / 2.0;
-    let x_min: f32 = 0.0 + half_enemy_size;
-    let x_max: f32 = window.width() - half_enemy_size;
-    let y_min: f32 = 0.0 + half_enemy_size;
-    let y_max: f32 = window.height() - half_enemy_size;
-
-    for mut transform in enemy_query.iter_mut(){
-        let mut translation = transform.translation;
-        
-        // Bound the enemy x position
-        if translation.x < x_min{
-            translation.x = x_min;
-        }else if translation.x > x_max{
-            translation.x = x_max;
-        }
+// Run condition gating the simulation systems so a synchronized pause freezes
+// movement, physics and collision on both peers at once.
+pub fn not_paused(pause: Res<PauseState>) -> bool {
+    !pause.paused
+}
 
-        // Bound the enemy y position
-        if translation.y < y_min {
-            translation.y = y_min;
-        } else if translation.y > y_max {
-            translation.y = y_max;
-        }
+// draws the game-over overlay with the restart prompt
+pub fn spawn_game_over(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                ..default()
+            },
+            GameOverUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Game Over",
+                TextStyle {
+                    font_size: 80.0,
+                    color: Color::RED,
+                    ..default()
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                "Press Enter to restart",
+                TextStyle {
+                    font_size: 32.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
+}
 
-        transform.translation = translation;
+pub fn despawn_game_over(mut commands: Commands, game_over_query: Query<Entity, With<GameOverUi>>) {
+    for entity in game_over_query.iter() {
+        commands.entity(entity).despawn_recursive();
     }
 }
 
-pub fn enemy_hit_player(
+// Clear the previous run's entities when leaving the game-over screen, so the
+// Playing OnEnter spawns a clean arena rather than stacking on the old one. The
+// walls are included: spawn_walls re-runs on restart (no_player_exists is true),
+// so without this the four static colliders would pile up every run.
+pub fn cleanup_arena(
     mut commands: Commands,
-    mut player_query: Query<(Entity, &Transform), With<Player>>,
-    enemy_query: Query<&Transform, With<Enemy>>,
-    // asset_server: Res<AssetServer>,
-    // audio: Res<Audio>,
-){
-    if let Ok((player_entity, player_transform)) = player_query.get_single_mut(){
-        for enemy_transform in enemy_query.iter(){
-            let distance: f32  = player_transform
-                .translation
-                .distance(enemy_transform.translation);
-            let player_radius = PLAYER_SIZE / 2.0;
-            let enemy_radius = ENEMY_SIZE / 2.0;
-            if distance < player_radius + enemy_radius {
-                println!("Enemy hit player! Game over!");
-                commands.entity(player_entity).despawn();
-            }
-        }
+    entity_query: Query<Entity, Or<(With<Player>, With<Enemy>, With<Star>, With<AreaWall>)>>,
+) {
+    for entity in entity_query.iter() {
+        commands.entity(entity).despawn();
     }
 }
 
-pub fn player_hit_star(
-    mut commands: Commands,
-    player_query: Query<&Transform, With<Player>>,
-    star_query: Query<(Entity, &Transform), With<Star>>,
-    // asset_server: Res<AssetServer>,
-){
-    if let Ok(player_transform) = player_query.get_single(){
-        for(star_entity, star_transform) in star_query.iter(){
-            let distance = player_transform
-                .translation
-                .distance(star_transform.translation);
-            if distance < PLAYER_SIZE / 2.0 + STAR_SIZE / 2.0 {
-                println!("Player hit star!");
-                commands.entity(star_entity).despawn();
-            }
-        }
-    }
+// Run condition: true only while the arena is empty, so the Playing spawns fire
+// on a fresh start (from Menu or a restart) but never on top of a live arena.
+// Pausing only sets PauseState and never leaves AppState::Playing.
+pub fn no_player_exists(player_query: Query<(), With<Player>>) -> bool {
+    player_query.is_empty()
 }
 
+// Spawn the four static walls around the window so everything stays contained
+// through real collision response instead of per-frame position clamping.
+pub fn spawn_walls(mut commands: Commands, window_query: Query<&Window, With<PrimaryWindow>>) {
+    let window = window_query.get_single().unwrap();
+    let w = window.width();
+    let h = window.height();
+    let half = WALL_THICKNESS / 2.0;
+
+    // (center position, half-extents) for left, right, bottom and top walls.
+    let walls = [
+        (Vec2::new(-half, h / 2.0), Vec2::new(half, h / 2.0)),
+        (Vec2::new(w + half, h / 2.0), Vec2::new(half, h / 2.0)),
+        (Vec2::new(w / 2.0, -half), Vec2::new(w / 2.0, half)),
+        (Vec2::new(w / 2.0, h + half), Vec2::new(w / 2.0, half)),
+    ];
+
+    for (center, half_extents) in walls {
+        commands.spawn((
+            TransformBundle::from(Transform::from_xyz(center.x, center.y, 0.0)),
+            RigidBody::Fixed,
+            Collider::cuboid(half_extents.x, half_extents.y),
+            // Perfect bounce so enemies reflect off the walls physically.
+            Restitution::coefficient(1.0),
+            AreaWall,
+        ));
+    }
+}
@@ -0,0 +1,224 @@
+// GPU particle feedback for the two core interactions: a golden sparkle shower
+// when a star is collected and a red explosion when the player is hit. The two
+// effects are built once at startup and handed to gameplay systems through a
+// resource; each event spawns a one-shot burst entity that cleans itself up
+// after its particles have faded.
+
+use bevy::prelude::*;
+use bevy_hanabi::prelude::*;
+
+use crate::assets::GameAssets;
+use crate::camera::CameraTarget;
+use crate::star::Score;
+use crate::{AppState, GameFlow};
+
+// Handles to the two preloaded burst effects, so the interaction systems emit a
+// spawner without rebuilding the `EffectAsset` on every pickup/death.
+#[derive(Resource)]
+pub struct ParticleEffects {
+    pub star_pickup: Handle<EffectAsset>,
+    pub player_death: Handle<EffectAsset>,
+}
+
+// How long a burst entity lingers before despawning; matches the particle
+// lifetime so the emitter is gone once nothing is left to draw.
+pub const BURST_LIFETIME: f32 = 1.0;
+
+// Despawns a one-shot burst emitter once its particles have finished.
+#[derive(Component)]
+pub struct BurstCleanup {
+    pub timer: Timer,
+}
+
+impl Default for BurstCleanup {
+    fn default() -> Self {
+        BurstCleanup {
+            timer: Timer::from_seconds(BURST_LIFETIME, TimerMode::Once),
+        }
+    }
+}
+
+// Non-rollback view of the confirmed simulation, so cosmetic feedback fires once
+// per real event rather than once per re-simulated frame: we react to the score
+// changing and to the game-over transition, both observed in the main schedule
+// outside GgrsSchedule.
+#[derive(Resource, Default)]
+pub struct FxState {
+    // Score the last pickup burst reacted to.
+    pub last_score: u32,
+    // Local player's most recent position, used to place the pickup burst (the
+    // collected star is already gone from the simulation by the time we react).
+    pub last_player_pos: Vec3,
+}
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(HanabiPlugin)
+            .init_resource::<FxState>()
+            .add_systems(Startup, setup_effects)
+            .add_systems(OnEnter(AppState::Playing), reset_fx_state)
+            // The death burst/sound reacts to the one-shot GameOver transition.
+            .add_systems(OnEnter(AppState::GameOver), react_player_death)
+            .add_systems(
+                Update,
+                (track_player_pos, react_star_pickups).run_if(in_state(AppState::Playing)),
+            )
+            .add_systems(Update, cleanup_bursts);
+    }
+}
+
+// Builds both burst effects and stores their handles. Each is a radial shower
+// with gravity-like drag and a colour that fades to transparent over a ~1s
+// lifetime.
+fn setup_effects(mut commands: Commands, mut effects: ResMut<Assets<EffectAsset>>) {
+    let star_pickup = effects.add(burst_effect(
+        Vec4::new(1.0, 0.85, 0.2, 1.0),
+        6.0,
+        250.0,
+    ));
+    let player_death = effects.add(burst_effect(
+        Vec4::new(1.0, 0.1, 0.1, 1.0),
+        10.0,
+        350.0,
+    ));
+
+    commands.insert_resource(ParticleEffects {
+        star_pickup,
+        player_death,
+    });
+}
+
+// A short-lived radial burst of `color` particles flung outwards at `speed` and
+// slowed by drag, fading to transparent as they shrink.
+fn burst_effect(color: Vec4, size: f32, speed: f32) -> EffectAsset {
+    let mut color_gradient = Gradient::new();
+    color_gradient.add_key(0.0, color);
+    color_gradient.add_key(1.0, Vec4::new(color.x, color.y, color.z, 0.0));
+
+    let mut size_gradient = Gradient::new();
+    size_gradient.add_key(0.0, Vec2::splat(size));
+    size_gradient.add_key(1.0, Vec2::ZERO);
+
+    let writer = ExprWriter::new();
+
+    let init_age = SetAttributeModifier::new(Attribute::AGE, writer.lit(0.0).expr());
+    let init_lifetime =
+        SetAttributeModifier::new(Attribute::LIFETIME, writer.lit(BURST_LIFETIME).expr());
+
+    // Emit from a small sphere and fling the particles radially outwards.
+    let init_pos = SetPositionSphereModifier {
+        center: writer.lit(Vec3::ZERO).expr(),
+        radius: writer.lit(4.0).expr(),
+        dimension: ShapeDimension::Volume,
+    };
+    let init_vel = SetVelocitySphereModifier {
+        center: writer.lit(Vec3::ZERO).expr(),
+        speed: writer.lit(speed).expr(),
+    };
+
+    // Drag stands in for gravity here: it brakes the shower so it settles
+    // instead of flying off screen.
+    let drag = LinearDragModifier::new(writer.lit(4.0).expr());
+
+    EffectAsset::new(256, Spawner::once(80.0.into(), true), writer.finish())
+        .init(init_pos)
+        .init(init_vel)
+        .init(init_age)
+        .init(init_lifetime)
+        .update(drag)
+        .render(ColorOverLifetimeModifier {
+            gradient: color_gradient,
+        })
+        .render(SizeOverLifetimeModifier {
+            gradient: size_gradient,
+            screen_space_size: false,
+        })
+}
+
+// Spawn a one-shot burst of `effect` at `position`. The death reaction passes
+// the hit player's recorded position; the pickup reaction passes the local
+// player's last position, not the collected star's, so a star picked up by the
+// other player bursts at the local ball.
+pub fn spawn_burst(commands: &mut Commands, effect: Handle<EffectAsset>, position: Vec3) {
+    commands.spawn((
+        ParticleEffectBundle {
+            effect: ParticleEffect::new(effect),
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        BurstCleanup::default(),
+    ));
+}
+
+// Clear the confirmed-event bookkeeping at the start of each run so a restart
+// doesn't replay the previous run's pickups.
+fn reset_fx_state(mut fx: ResMut<FxState>) {
+    fx.last_score = 0;
+}
+
+// Remember where the local player is so the pickup burst can be placed near it
+// once the collected star has left the simulation.
+fn track_player_pos(
+    mut fx: ResMut<FxState>,
+    player_query: Query<&Transform, With<CameraTarget>>,
+) {
+    if let Ok(transform) = player_query.get_single() {
+        fx.last_player_pos = transform.translation;
+    }
+}
+
+// Fire a golden sparkle shower and pickup chime whenever the confirmed score
+// climbs. Reading the net delta here collapses the duplicate increments a
+// rollback would otherwise produce down to one burst per real pickup.
+fn react_star_pickups(
+    mut commands: Commands,
+    mut fx: ResMut<FxState>,
+    score: Res<Score>,
+    assets: Res<GameAssets>,
+    effects: Res<ParticleEffects>,
+) {
+    if score.0 > fx.last_score {
+        for _ in fx.last_score..score.0 {
+            commands.spawn(AudioBundle {
+                source: assets.pickup_sfx.clone(),
+                settings: PlaybackSettings::DESPAWN,
+            });
+            spawn_burst(&mut commands, effects.star_pickup.clone(), fx.last_player_pos);
+        }
+    }
+    // Resync on rollbacks that lowered the score so we don't double-count later.
+    fx.last_score = score.0;
+}
+
+// Fire the red explosion and game-over sound once, when the simulation has
+// confirmed the player was hit and transitioned to GameOver. The burst is placed
+// at the hit player's recorded position, so it lands correctly even when the
+// player that died is not the locally-followed one.
+fn react_player_death(
+    mut commands: Commands,
+    flow: Res<GameFlow>,
+    assets: Res<GameAssets>,
+    effects: Res<ParticleEffects>,
+) {
+    commands.spawn(AudioBundle {
+        source: assets.game_over_sfx.clone(),
+        settings: PlaybackSettings::DESPAWN,
+    });
+    spawn_burst(&mut commands, effects.player_death.clone(), flow.death_pos);
+}
+
+// Tick each burst's timer and remove the emitter once its particles have faded,
+// so one-shot effects don't leak entities.
+fn cleanup_bursts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut burst_query: Query<(Entity, &mut BurstCleanup)>,
+) {
+    for (entity, mut burst) in burst_query.iter_mut() {
+        if burst.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// Owns the player ball: spawning one per networked player and driving each
+// body's velocity from its rolled-back input.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_ggrs::{AddRollbackCommandExtension, GgrsSchedule, PlayerInputs};
+use bevy_rapier2d::prelude::*;
+
+use crate::assets::GameAssets;
+use crate::camera::CameraTarget;
+use crate::{
+    no_player_exists, not_paused, AppState, GgrsConfig, INPUT_DOWN, INPUT_LEFT, INPUT_RIGHT,
+    INPUT_UP, NUM_PLAYERS, PLAYER_SIZE, PLAYER_SPEED,
+};
+
+// Component
+#[derive(Component)]
+pub struct Player {
+    // Which networked player controls this ball (0 or 1).
+    pub handle: usize,
+}
+
+pub struct PlayerPlugin;
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(AppState::Playing),
+            spawn_player.run_if(no_player_exists),
+        )
+        .add_systems(
+            GgrsSchedule,
+            // Set velocities before the physics step integrates them.
+            player_movement
+                .before(PhysicsSet::SyncBackend)
+                .run_if(in_state(AppState::Playing))
+                .run_if(not_paused),
+        );
+    }
+}
+
+// 1. create one player entity per networked player
+// 2. places the balls side by side around the center of the game window
+// 3. gives each the preloaded player sprite
+pub fn spawn_player(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    assets: Res<GameAssets>,
+){
+    let window = window_query.get_single().unwrap();
+
+    // One ball per networked player, placed symmetrically so the layout is
+    // identical on both machines.
+    for handle in 0..NUM_PLAYERS {
+        let offset = (handle as f32 - (NUM_PLAYERS as f32 - 1.0) / 2.0) * PLAYER_SIZE * 2.0;
+        let mut entity = commands
+            .spawn((
+                // Bundles - can quickly add/remove sets of components to or from an entity
+                SpriteBundle {
+                    transform: Transform::from_xyz(
+                        window.width() / 2.0 + offset,
+                        window.height() / 2.0,
+                        0.0,
+                    ),
+                    texture: assets.player.clone(),
+                    ..default()
+                },
+                Player { handle },
+                // Dynamic body so the walls physically stop it; velocity is
+                // driven each frame from the player's input.
+                RigidBody::Dynamic,
+                Collider::ball(PLAYER_SIZE / 2.0),
+                Velocity::zero(),
+                LockedAxes::ROTATION_LOCKED,
+            ));
+        entity.add_rollback();
+        // The camera follows the local player's ball.
+        if handle == 0 {
+            entity.insert(CameraTarget);
+        }
+    }
+}
+
+// handles every player's movement from the rolled-back network inputs
+pub fn player_movement(
+    // the per-frame input bitmask for every player, supplied by GGRS
+    inputs: Res<PlayerInputs<GgrsConfig>>,
+    mut player_query: Query<(&mut Velocity, &Player)>,
+){
+    for (mut velocity, player) in player_query.iter_mut() {
+        // inputs[handle].0 is the bitmask this player submitted for the frame
+        // being (re)simulated.
+        let (input, _) = inputs[player.handle];
+
+        // Vec2 heading assembled from the held direction bits.
+        let mut direction = Vec2::ZERO;
+
+        if input & INPUT_LEFT != 0 {
+            direction.x -= 1.0;
+        }
+        if input & INPUT_RIGHT != 0 {
+            direction.x += 1.0;
+        }
+        if input & INPUT_UP != 0 {
+            direction.y += 1.0;
+        }
+        if input & INPUT_DOWN != 0 {
+            direction.y -= 1.0;
+        }
+
+        // direction.normalize() normalizes the vector
+        // Normalization is a process that adjusts the vector so that its length (magnitude) is exactly 1, but it keeps pointing in the same direction.
+        if direction.length() > 0.0 {
+            direction = direction.normalize();
+        }
+
+        // Drive the rigid body through its velocity; the physics step and the
+        // arena walls take care of integration and confinement.
+        velocity.linvel = direction * PLAYER_SPEED;
+    }
+}
@@ -0,0 +1,198 @@
+// Owns the collectible stars, the score tally, and the score readout UI.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_ggrs::{AddRollbackCommandExtension, GgrsSchedule};
+use bevy_rapier2d::prelude::*;
+
+use crate::assets::GameAssets;
+use crate::player::Player;
+use crate::{
+    no_player_exists, not_paused, AppState, RollbackRng, FIXED_TIMESTEP, NUMBER_OF_STARS,
+    STAR_SIZE, STAR_SPAWN_TIME,
+};
+
+#[derive(Component)]
+pub struct Star {}
+
+// Running tally of stars the player has collected this run.
+#[derive(Resource, Clone, Copy)]
+pub struct Score(pub u32);
+
+impl Default for Score {
+    fn default() -> Self {
+        Score(0)
+    }
+}
+
+// Fires on a repeating interval to drop a new star somewhere in the window.
+#[derive(Resource, Clone)]
+pub struct StarSpawnTimer {
+    pub timer: Timer,
+}
+
+impl Default for StarSpawnTimer {
+    fn default() -> Self {
+        StarSpawnTimer {
+            timer: Timer::from_seconds(STAR_SPAWN_TIME, TimerMode::Repeating),
+        }
+    }
+}
+
+// Marker for the score readout so update_score_ui can find it.
+#[derive(Component)]
+pub struct ScoreText;
+
+pub struct StarPlugin;
+
+impl Plugin for StarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Score>()
+            .init_resource::<StarSpawnTimer>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                (spawn_stars, spawn_score_ui, reset_score).run_if(no_player_exists),
+            )
+            .add_systems(OnEnter(AppState::GameOver), despawn_score_ui)
+            .add_systems(
+                GgrsSchedule,
+                (
+                    // New stars must exist before the step syncs colliders.
+                    spawn_stars_over_time.before(PhysicsSet::SyncBackend),
+                    // Pickups are read from the poses the step wrote back.
+                    player_hit_star.after(PhysicsSet::Writeback),
+                )
+                    .run_if(in_state(AppState::Playing))
+                    .run_if(not_paused),
+            )
+            .add_systems(Update, update_score_ui.run_if(in_state(AppState::Playing)));
+    }
+}
+
+pub fn spawn_stars(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    assets: Res<GameAssets>,
+    mut rng: ResMut<RollbackRng>,
+){
+    let window = window_query.get_single().unwrap();
+    for _ in 0..NUMBER_OF_STARS{
+        let random_x = rng.unit() * window.width();
+        let random_y = rng.unit() * window.height();
+
+        commands
+            .spawn((
+                SpriteBundle{
+                    transform: Transform::from_xyz(random_x, random_y, 0.0),
+                    texture: assets.star.clone(),
+                    ..default()
+                },
+                Star{},
+                // Sensors report overlaps without a physical response, so the
+                // player passes through and we despawn once the overlap shows up
+                // in the step's intersection graph.
+                Collider::ball(STAR_SIZE / 2.0),
+                Sensor,
+            ))
+            .add_rollback();
+    }
+}
+
+// drop a new star at a random position whenever the star timer fires
+pub fn spawn_stars_over_time(
+    mut commands: Commands,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    assets: Res<GameAssets>,
+    mut star_timer: ResMut<StarSpawnTimer>,
+    mut rng: ResMut<RollbackRng>,
+) {
+    // Fixed step keeps the spawn cadence identical on both peers.
+    star_timer
+        .timer
+        .tick(std::time::Duration::from_secs_f32(FIXED_TIMESTEP));
+
+    if star_timer.timer.finished() {
+        let window = window_query.get_single().unwrap();
+        let random_x = rng.unit() * window.width();
+        let random_y = rng.unit() * window.height();
+
+        commands
+            .spawn((
+                SpriteBundle {
+                    transform: Transform::from_xyz(random_x, random_y, 0.0),
+                    texture: assets.star.clone(),
+                    ..default()
+                },
+                Star {},
+                Collider::ball(STAR_SIZE / 2.0),
+                Sensor,
+            ))
+            .add_rollback();
+    }
+}
+
+pub fn player_hit_star(
+    mut commands: Commands,
+    // The step's sensor-overlap graph, queried deterministically in the fixed
+    // step rather than drained from render-cadence CollisionEvents.
+    rapier_context: Res<RapierContext>,
+    player_query: Query<Entity, With<Player>>,
+    star_query: Query<Entity, With<Star>>,
+    mut score: ResMut<Score>,
+){
+    for star_entity in star_query.iter() {
+        // A star is collected as soon as any player overlaps its sensor.
+        let collected = player_query.iter().any(|player_entity| {
+            rapier_context
+                .intersection_pair(player_entity, star_entity)
+                .unwrap_or(false)
+        });
+
+        if collected {
+            println!("Player hit star!");
+            commands.entity(star_entity).despawn_recursive();
+            score.0 += 1;
+        }
+    }
+}
+
+// draws the score readout in the top-left corner
+pub fn spawn_score_ui(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "Score: 0",
+            TextStyle {
+                font_size: 32.0,
+                color: Color::WHITE,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        }),
+        ScoreText,
+    ));
+}
+
+pub fn despawn_score_ui(mut commands: Commands, score_query: Query<Entity, With<ScoreText>>) {
+    for entity in score_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// keeps the score readout in sync with the Score resource
+pub fn update_score_ui(score: Res<Score>, mut score_query: Query<&mut Text, With<ScoreText>>) {
+    if score.is_changed() {
+        if let Ok(mut text) = score_query.get_single_mut() {
+            text.sections[0].value = format!("Score: {}", score.0);
+        }
+    }
+}
+
+// reset the tally at the start of each run
+pub fn reset_score(mut score: ResMut<Score>) {
+    score.0 = 0;
+}